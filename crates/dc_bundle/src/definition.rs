@@ -0,0 +1,285 @@
+// Copyright 2024 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of the current (non-legacy) Figma document schema into the
+//! in-memory types used by the rest of the crate.
+
+use crate::{Error, PathSegment, Result};
+
+/// Controls how the converter reacts to an enum tag it doesn't recognize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecodeMode {
+    /// Unknown enum variants are a hard, fatal error. This is the default,
+    /// matching today's behavior for callers that expect a fully-known
+    /// document.
+    #[default]
+    Strict,
+    /// Unknown enum variants are captured into an `Unknown` carrier instead
+    /// of aborting, so a document written by a newer Figma plugin can still
+    /// be loaded by an older runtime.
+    NonExhaustive,
+}
+
+/// The kind of fill applied to a node's background or stroke.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FillType {
+    Solid,
+    GradientLinear,
+    GradientRadial,
+    Image,
+    /// A tag this build of the crate doesn't recognize. Preserved losslessly
+    /// via [`FillType::encode`] so it can be re-serialized unchanged. Only
+    /// produced when decoding with [`DecodeMode::NonExhaustive`].
+    Unknown { enum_name: String, raw_tag: String, raw_payload: serde_json::Value },
+}
+
+impl FillType {
+    const VARIANTS: &'static [&'static str] = &["Solid", "GradientLinear", "GradientRadial", "Image"];
+
+    /// Decodes a Figma fill type tag, e.g. `"Solid"`, into a [`FillType`].
+    pub fn decode(tag: &str, raw_payload: &serde_json::Value, mode: DecodeMode) -> Result<Self> {
+        match tag {
+            "Solid" => Ok(FillType::Solid),
+            "GradientLinear" => Ok(FillType::GradientLinear),
+            "GradientRadial" => Ok(FillType::GradientRadial),
+            "Image" => Ok(FillType::Image),
+            _ if mode == DecodeMode::NonExhaustive => Ok(FillType::Unknown {
+                enum_name: "FillType".to_string(),
+                raw_tag: tag.to_string(),
+                raw_payload: raw_payload.clone(),
+            }),
+            _ => Err(Error::unknown_enum_variant("FillType", tag, Self::VARIANTS.iter().copied())),
+        }
+    }
+
+    /// Encodes a `FillType` back into its raw JSON representation,
+    /// completing the round-trip promised by [`DecodeMode::NonExhaustive`]
+    /// decoding: known variants re-encode to just their tag (see
+    /// [`crate::utils::tagged`]); [`FillType::Unknown`] replays the
+    /// original payload verbatim.
+    pub fn encode(&self) -> serde_json::Value {
+        match self {
+            FillType::Solid => crate::utils::tagged("Solid"),
+            FillType::GradientLinear => crate::utils::tagged("GradientLinear"),
+            FillType::GradientRadial => crate::utils::tagged("GradientRadial"),
+            FillType::Image => crate::utils::tagged("Image"),
+            FillType::Unknown { raw_payload, .. } => raw_payload.clone(),
+        }
+    }
+}
+
+impl Default for FillType {
+    /// The placeholder substituted for a fill that failed to decode when
+    /// converting with [`Frame::decode_collecting_errors`].
+    fn default() -> Self {
+        FillType::Solid
+    }
+}
+
+/// A rectangular node's visual style.
+#[derive(Debug, Clone, Default)]
+pub struct Frame {
+    pub corner_radius: f32,
+    pub fills: Vec<FillType>,
+}
+
+impl Frame {
+    /// Decodes a `Frame` from its raw JSON representation, collecting every
+    /// missing-field and unknown-enum-variant error found instead of
+    /// failing on the first one. A default/placeholder value is substituted
+    /// for each broken field so conversion can keep descending.
+    pub fn decode_collecting_errors(raw: &serde_json::Value) -> Result<Frame> {
+        let mut errors = crate::utils::ErrorCollector::new();
+        let path = crate::utils::PathScope::new();
+        let present_keys: Vec<&str> =
+            raw.as_object().map(|obj| obj.keys().map(String::as_str).collect()).unwrap_or_default();
+
+        // A field that's present but the wrong shape is reported as missing,
+        // like an absent `cornerRadius` (an absent `fills` is not an error);
+        // either way, don't suggest the field itself as a "did you mean"
+        // candidate.
+        fn other_keys<'a>(present_keys: &'a [&'a str], field: &'a str) -> impl Iterator<Item = &'a str> {
+            present_keys.iter().copied().filter(move |&k| k != field)
+        }
+
+        let corner_radius = match raw.get("cornerRadius").and_then(|v| v.as_f64()) {
+            Some(value) => value as f32,
+            None => errors.record(Error::missing_field(
+                "cornerRadius",
+                path.segments(),
+                other_keys(&present_keys, "cornerRadius"),
+            )),
+        };
+
+        let fills = match raw.get("fills") {
+            None => Vec::new(),
+            Some(value) => match value.as_array() {
+                None => errors.record(Error::missing_field(
+                    "fills",
+                    path.segments(),
+                    other_keys(&present_keys, "fills"),
+                )),
+                Some(array) => {
+                    let _fills_guard = path.enter(PathSegment::Key("fills".to_string()));
+                    array
+                        .iter()
+                        .enumerate()
+                        .map(|(index, fill)| {
+                            let _index_guard = path.enter(PathSegment::Index(index));
+                            let fill_keys: Vec<&str> = fill
+                                .as_object()
+                                .map(|obj| obj.keys().map(String::as_str).collect())
+                                .unwrap_or_default();
+                            match fill.get("type").and_then(|v| v.as_str()) {
+                                Some(tag) => match FillType::decode(tag, fill, DecodeMode::Strict) {
+                                    Ok(fill_type) => fill_type,
+                                    Err(error) => errors.record(error),
+                                },
+                                None => errors.record(Error::missing_field(
+                                    "type",
+                                    path.segments(),
+                                    other_keys(&fill_keys, "type"),
+                                )),
+                            }
+                        })
+                        .collect()
+                }
+            },
+        };
+
+        errors.finish(Frame { corner_radius, fills })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn fill_type_decode_rejects_unknown_tag_in_strict_mode() {
+        let payload = json!({"type": "Fxed"});
+        match FillType::decode("Fxed", &payload, DecodeMode::Strict) {
+            Err(Error::UnknownEnumVariant { enum_name, tag, .. }) => {
+                assert_eq!(enum_name, "FillType");
+                assert_eq!(tag, "Fxed");
+            }
+            other => panic!("expected Err(UnknownEnumVariant), got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn fill_type_decode_non_exhaustive_round_trips_unknown_tag() {
+        let payload = json!({"type": "NeonGlow", "intensity": 5});
+        let decoded = FillType::decode("NeonGlow", &payload, DecodeMode::NonExhaustive).unwrap();
+        assert_eq!(decoded.encode(), payload);
+    }
+
+    #[test]
+    fn fill_type_known_variant_round_trips_through_encode() {
+        let payload = json!({"type": "Solid"});
+        let decoded = FillType::decode("Solid", &payload, DecodeMode::Strict).unwrap();
+        assert_eq!(decoded.encode(), payload);
+    }
+
+    #[test]
+    fn frame_decode_collecting_errors_reports_missing_field_and_unknown_variant() {
+        let raw = json!({
+            "fills": [
+                {"type": "Solid"},
+                {"type": "Fxed"},
+            ]
+        });
+        let errors = match Frame::decode_collecting_errors(&raw) {
+            Err(Error::Errors(errors)) => errors,
+            other => panic!("expected Err(Error::Errors(..)), got {other:?}"),
+        };
+        assert_eq!(errors.len(), 2);
+
+        match &errors[0] {
+            Error::MissingFieldError { field, .. } => assert_eq!(field, "cornerRadius"),
+            other => panic!("expected MissingFieldError, got {other:?}"),
+        }
+        match &errors[1] {
+            Error::UnknownEnumVariant { enum_name, tag, .. } => {
+                assert_eq!(enum_name, "FillType");
+                assert_eq!(tag, "Fxed");
+            }
+            other => panic!("expected UnknownEnumVariant, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn frame_decode_collecting_errors_attaches_field_path_breadcrumbs() {
+        let raw = json!({
+            "cornerRadius": 4.0,
+            "fills": [
+                {"type": "Solid"},
+                {},
+            ]
+        });
+        let errors = match Frame::decode_collecting_errors(&raw) {
+            Err(Error::Errors(errors)) => errors,
+            other => panic!("expected Err(Error::Errors(..)), got {other:?}"),
+        };
+        assert_eq!(errors.len(), 1);
+
+        match &errors[0] {
+            Error::MissingFieldError { field, field_path, .. } => {
+                assert_eq!(field, "type");
+                assert_eq!(
+                    field_path,
+                    &vec![PathSegment::Key("fills".to_string()), PathSegment::Index(1)]
+                );
+            }
+            other => panic!("expected MissingFieldError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn frame_decode_collecting_errors_does_not_suggest_a_present_but_wrong_typed_field_as_its_own_fix() {
+        let raw = json!({"cornerRadius": "not a number", "fills": []});
+        let errors = match Frame::decode_collecting_errors(&raw) {
+            Err(Error::Errors(errors)) => errors,
+            other => panic!("expected Err(Error::Errors(..)), got {other:?}"),
+        };
+        assert_eq!(errors.len(), 1);
+
+        match &errors[0] {
+            Error::MissingFieldError { field, suggestion, .. } => {
+                assert_eq!(field, "cornerRadius");
+                assert_ne!(suggestion.as_deref(), Some("cornerRadius"));
+            }
+            other => panic!("expected MissingFieldError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn frame_decode_collecting_errors_does_not_suggest_type_as_its_own_fix() {
+        let raw = json!({"cornerRadius": 4.0, "fills": [{"type": 123}]});
+        let errors = match Frame::decode_collecting_errors(&raw) {
+            Err(Error::Errors(errors)) => errors,
+            other => panic!("expected Err(Error::Errors(..)), got {other:?}"),
+        };
+        assert_eq!(errors.len(), 1);
+
+        match &errors[0] {
+            Error::MissingFieldError { field, suggestion, .. } => {
+                assert_eq!(field, "type");
+                assert_ne!(suggestion.as_deref(), Some("type"));
+            }
+            other => panic!("expected MissingFieldError, got {other:?}"),
+        }
+    }
+}
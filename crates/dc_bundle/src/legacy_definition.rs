@@ -0,0 +1,95 @@
+// Copyright 2024 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of the legacy Figma document schema, kept around so older
+//! documents and Figma plugin versions can still be loaded.
+
+use crate::definition::DecodeMode;
+use crate::{Error, Result};
+
+/// The legacy fill type tag set. Smaller than [`crate::definition::FillType`]
+/// since newer fill kinds were added after this schema was frozen.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LegacyFillType {
+    Solid,
+    GradientLinear,
+    /// A tag this build of the crate doesn't recognize, preserved the same
+    /// way as [`crate::definition::FillType::Unknown`] — see its docs for
+    /// why.
+    Unknown { enum_name: String, raw_tag: String, raw_payload: serde_json::Value },
+}
+
+impl LegacyFillType {
+    const VARIANTS: &'static [&'static str] = &["Solid", "GradientLinear"];
+
+    /// Decodes a legacy fill type tag into a [`LegacyFillType`].
+    pub fn decode(tag: &str, raw_payload: &serde_json::Value, mode: DecodeMode) -> Result<Self> {
+        match tag {
+            "Solid" => Ok(LegacyFillType::Solid),
+            "GradientLinear" => Ok(LegacyFillType::GradientLinear),
+            _ if mode == DecodeMode::NonExhaustive => Ok(LegacyFillType::Unknown {
+                enum_name: "LegacyFillType".to_string(),
+                raw_tag: tag.to_string(),
+                raw_payload: raw_payload.clone(),
+            }),
+            _ => Err(Error::unknown_enum_variant(
+                "LegacyFillType",
+                tag,
+                Self::VARIANTS.iter().copied(),
+            )),
+        }
+    }
+
+    /// Encodes a `LegacyFillType` back into its raw JSON representation;
+    /// see [`crate::definition::FillType::encode`] for the rationale.
+    pub fn encode(&self) -> serde_json::Value {
+        match self {
+            LegacyFillType::Solid => crate::utils::tagged("Solid"),
+            LegacyFillType::GradientLinear => crate::utils::tagged("GradientLinear"),
+            LegacyFillType::Unknown { raw_payload, .. } => raw_payload.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn legacy_fill_type_decode_rejects_unknown_tag_in_strict_mode() {
+        let payload = json!({"type": "Fxed"});
+        match LegacyFillType::decode("Fxed", &payload, DecodeMode::Strict) {
+            Err(Error::UnknownEnumVariant { enum_name, tag, .. }) => {
+                assert_eq!(enum_name, "LegacyFillType");
+                assert_eq!(tag, "Fxed");
+            }
+            other => panic!("expected Err(UnknownEnumVariant), got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn legacy_fill_type_decode_non_exhaustive_round_trips_unknown_tag() {
+        let payload = json!({"type": "NeonGlow", "intensity": 5});
+        let decoded = LegacyFillType::decode("NeonGlow", &payload, DecodeMode::NonExhaustive).unwrap();
+        assert_eq!(decoded.encode(), payload);
+    }
+
+    #[test]
+    fn legacy_fill_type_known_variant_round_trips_through_encode() {
+        let payload = json!({"type": "Solid"});
+        let decoded = LegacyFillType::decode("Solid", &payload, DecodeMode::Strict).unwrap();
+        assert_eq!(decoded.encode(), payload);
+    }
+}
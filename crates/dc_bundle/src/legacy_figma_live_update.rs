@@ -0,0 +1,47 @@
+// Copyright 2024 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of incremental document updates pushed live by the Figma
+//! plugin while the user is editing.
+
+use crate::definition::{DecodeMode, FillType};
+use crate::Result;
+
+/// Decodes a single fill update pushed by the Figma plugin.
+///
+/// Live updates can reference schema additions from a newer plugin version
+/// before this runtime has caught up, so they are always decoded in
+/// [`DecodeMode::NonExhaustive`] rather than failing the whole update.
+pub fn decode_fill_update(tag: &str, raw_payload: &serde_json::Value) -> Result<FillType> {
+    FillType::decode(tag, raw_payload, DecodeMode::NonExhaustive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn decode_fill_update_tolerates_an_unrecognized_tag() {
+        let payload = json!({"type": "NeonGlow", "intensity": 5});
+        let decoded = decode_fill_update("NeonGlow", &payload).unwrap();
+        assert_eq!(decoded.encode(), payload);
+    }
+
+    #[test]
+    fn decode_fill_update_decodes_a_known_tag() {
+        let payload = json!({"type": "Solid"});
+        assert_eq!(decode_fill_update("Solid", &payload).unwrap(), FillType::Solid);
+    }
+}
@@ -21,12 +21,55 @@ pub mod definition;
 pub mod legacy_definition;
 pub mod legacy_figma_live_update;
 
+/// One step in a [`Error::MissingFieldError`]'s `field_path`, describing how
+/// the converter reached the node that was missing a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A named field or object key, e.g. `style`.
+    Key(String),
+    /// An index into an array, e.g. the 2nd element of `background`.
+    Index(usize),
+}
+
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("Missing field {field}")]
-    MissingFieldError { field: String },
-    #[error("Unknown enum variant for {enum_name}")]
-    UnknownEnumVariant { enum_name: String },
+    #[error("Missing field at {}{}", utils::format_field_path(field_path, field), utils::format_suggestion(suggestion))]
+    MissingFieldError { field: String, field_path: Vec<PathSegment>, suggestion: Option<String> },
+    #[error("Unknown enum variant '{tag}' for {enum_name}{}", utils::format_suggestion(suggestion))]
+    UnknownEnumVariant { enum_name: String, tag: String, suggestion: Option<String> },
+    /// Multiple errors collected from a single conversion pass. Produced by
+    /// entry points that keep descending after a recoverable failure
+    /// instead of aborting on the first one.
+    #[error("{} errors occurred during conversion:\n{}", .0.len(), utils::format_error_list(.0))]
+    Errors(Vec<Error>),
+}
+
+impl Error {
+    /// Builds a [`Error::MissingFieldError`] for `field` at `field_path`,
+    /// suggesting the closest match in `known_fields` if one is close
+    /// enough to `field` to likely be a typo.
+    pub fn missing_field<'a>(
+        field: impl Into<String>,
+        field_path: Vec<PathSegment>,
+        known_fields: impl Iterator<Item = &'a str>,
+    ) -> Self {
+        let field = field.into();
+        let suggestion = utils::find_closest_match(&field, known_fields);
+        Error::MissingFieldError { field, field_path, suggestion }
+    }
+
+    /// Builds a [`Error::UnknownEnumVariant`], suggesting the closest match
+    /// in `known_variants` if one is close enough to `tag` to likely be a
+    /// typo.
+    pub fn unknown_enum_variant<'a>(
+        enum_name: impl Into<String>,
+        tag: impl Into<String>,
+        known_variants: impl Iterator<Item = &'a str>,
+    ) -> Self {
+        let tag = tag.into();
+        let suggestion = utils::find_closest_match(&tag, known_variants);
+        Error::UnknownEnumVariant { enum_name: enum_name.into(), tag, suggestion }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,274 @@
+// Copyright 2024 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Small helpers shared by the conversion modules.
+
+/// Computes the Levenshtein edit distance between `a` and `b` (insert,
+/// delete and substitute all cost 1).
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=n {
+        for j in 1..=m {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1).min(dp[i][j - 1] + 1).min(dp[i - 1][j - 1] + cost);
+        }
+    }
+    dp[n][m]
+}
+
+/// Finds the candidate in `candidates` closest to `name`, for use in
+/// "did you mean" style suggestions. Mirrors rustc's field-suggestion
+/// heuristic: a candidate is only suggested when its edit distance to
+/// `name` is within `max(1, name.len() / 3)`.
+pub(crate) fn find_closest_match<'a>(
+    name: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> Option<String> {
+    let mut best: Option<(&str, usize)> = None;
+    for candidate in candidates {
+        let dist = levenshtein_distance(name, candidate);
+        let is_better = match best {
+            Some((_, best_dist)) => dist < best_dist,
+            None => true,
+        };
+        if is_better {
+            best = Some((candidate, dist));
+        }
+    }
+
+    let (candidate, dist) = best?;
+    let threshold = std::cmp::max(1, name.len() / 3);
+    (dist <= threshold).then(|| candidate.to_string())
+}
+
+/// Renders an optional suggestion as the `(did you mean 'x'?)` suffix used
+/// by [`crate::Error`]'s `Display` impl, or an empty string if there is
+/// none.
+pub(crate) fn format_suggestion(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(name) => format!(" (did you mean '{name}'?)"),
+        None => String::new(),
+    }
+}
+
+/// Builds the minimal `{"type": tag}` JSON object that a known enum variant
+/// re-encodes to. An unrecognized variant instead replays its stored
+/// payload verbatim; see [`crate::definition::FillType::encode`] and
+/// [`crate::legacy_definition::LegacyFillType::encode`].
+pub(crate) fn tagged(tag: &str) -> serde_json::Value {
+    serde_json::json!({"type": tag})
+}
+
+/// Renders the errors collected in an [`crate::Error::Errors`] as an
+/// indented bullet list, one per line.
+pub(crate) fn format_error_list(errors: &[crate::Error]) -> String {
+    errors.iter().map(|error| format!("  - {error}")).collect::<Vec<_>>().join("\n")
+}
+
+/// Renders a [`crate::Error::MissingFieldError`]'s `field_path` and `field`
+/// as a single breadcrumb, e.g. `frame[2].style.background[0].cornerRadius`.
+pub(crate) fn format_field_path(field_path: &[crate::PathSegment], field: &str) -> String {
+    let mut rendered = String::new();
+    for segment in field_path {
+        match segment {
+            crate::PathSegment::Key(key) => {
+                if !rendered.is_empty() {
+                    rendered.push('.');
+                }
+                rendered.push_str(key);
+            }
+            crate::PathSegment::Index(index) => {
+                rendered.push_str(&format!("[{index}]"));
+            }
+        }
+    }
+    if rendered.is_empty() {
+        field.to_string()
+    } else {
+        rendered.push('.');
+        rendered.push_str(field);
+        rendered
+    }
+}
+
+/// Tracks the breadcrumb trail of keys/indices the converter has descended
+/// through while decoding a document, for attaching to a
+/// [`crate::Error::MissingFieldError`] raised deeper in the recursion.
+///
+/// Uses a `RefCell` rather than plain `&mut` borrows so that nested
+/// [`PathGuard`]s (one per level of descent) can be held live at the same
+/// time, the same way `tracing`'s span guards do.
+#[derive(Debug, Default)]
+pub(crate) struct PathScope {
+    segments: std::cell::RefCell<Vec<crate::PathSegment>>,
+}
+
+impl PathScope {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pushes `segment` onto the path for the lifetime of the returned
+    /// guard; it is popped automatically when the guard drops, so callers
+    /// don't have to thread the path manually through recursive descent.
+    pub(crate) fn enter(&self, segment: crate::PathSegment) -> PathGuard<'_> {
+        self.segments.borrow_mut().push(segment);
+        PathGuard { scope: self }
+    }
+
+    /// Snapshots the current path, for attaching to an error raised at this
+    /// point in the descent.
+    pub(crate) fn segments(&self) -> Vec<crate::PathSegment> {
+        self.segments.borrow().clone()
+    }
+}
+
+pub(crate) struct PathGuard<'a> {
+    scope: &'a PathScope,
+}
+
+impl Drop for PathGuard<'_> {
+    fn drop(&mut self) {
+        self.scope.segments.borrow_mut().pop();
+    }
+}
+
+/// Accumulates conversion errors encountered while continuing to descend
+/// into a malformed document, instead of aborting on the first one.
+#[derive(Debug, Default)]
+pub(crate) struct ErrorCollector {
+    errors: Vec<crate::Error>,
+}
+
+impl ErrorCollector {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `error` and returns `T::default()` so the caller can
+    /// substitute a placeholder and keep descending.
+    pub(crate) fn record<T: Default>(&mut self, error: crate::Error) -> T {
+        self.errors.push(error);
+        T::default()
+    }
+
+    /// Resolves the collected errors into a `Result`: `Ok(value)` if
+    /// nothing was recorded, otherwise `Err(Error::Errors(..))` with every
+    /// error found during the pass.
+    pub(crate) fn finish<T>(self, value: T) -> crate::Result<T> {
+        if self.errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(crate::Error::Errors(self.errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PathSegment;
+
+    #[test]
+    fn find_closest_match_suggests_a_close_typo() {
+        let candidates = ["Solid", "GradientLinear", "Image"];
+        assert_eq!(find_closest_match("Solud", candidates.into_iter()), Some("Solid".to_string()));
+    }
+
+    #[test]
+    fn find_closest_match_rejects_a_candidate_that_is_too_far() {
+        let candidates = ["Solid", "GradientLinear", "Image"];
+        assert_eq!(find_closest_match("Xyz", candidates.into_iter()), None);
+    }
+
+    #[test]
+    fn find_closest_match_threshold_is_inclusive() {
+        // threshold = max(1, len/3) of the looked-up name; for "Fxed" (len 4) that's 1.
+        assert_eq!(find_closest_match("Fxed", ["Fixed"].into_iter()), Some("Fixed".to_string()));
+        // "Fxd" is distance 2 from "Fixed", past the threshold of 1.
+        assert_eq!(find_closest_match("Fxd", ["Fixed"].into_iter()), None);
+    }
+
+    #[test]
+    fn find_closest_match_with_no_candidates() {
+        assert_eq!(find_closest_match("anything", std::iter::empty()), None);
+    }
+
+    #[test]
+    fn error_collector_finish_is_ok_when_nothing_was_recorded() {
+        let errors = ErrorCollector::new();
+        assert!(matches!(errors.finish(42), Ok(42)));
+    }
+
+    #[test]
+    fn error_collector_finish_aggregates_every_recorded_error() {
+        let mut errors = ErrorCollector::new();
+        let _: f32 = errors.record(crate::Error::missing_field("a", Vec::new(), std::iter::empty()));
+        let _: f32 = errors.record(crate::Error::missing_field("b", Vec::new(), std::iter::empty()));
+
+        match errors.finish(()) {
+            Err(crate::Error::Errors(collected)) => assert_eq!(collected.len(), 2),
+            other => panic!("expected Err(Error::Errors(..)), got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn format_field_path_builds_the_expected_breadcrumb() {
+        let path = vec![
+            PathSegment::Key("frame".to_string()),
+            PathSegment::Index(2),
+            PathSegment::Key("style".to_string()),
+            PathSegment::Key("background".to_string()),
+            PathSegment::Index(0),
+        ];
+        assert_eq!(format_field_path(&path, "cornerRadius"), "frame[2].style.background[0].cornerRadius");
+    }
+
+    #[test]
+    fn format_field_path_with_no_path_is_just_the_field() {
+        assert_eq!(format_field_path(&[], "cornerRadius"), "cornerRadius");
+    }
+
+    #[test]
+    fn path_scope_guards_push_and_pop_in_order() {
+        let path = PathScope::new();
+        assert!(path.segments().is_empty());
+
+        let outer = path.enter(PathSegment::Key("frame".to_string()));
+        assert_eq!(path.segments(), vec![PathSegment::Key("frame".to_string())]);
+
+        {
+            let _inner = path.enter(PathSegment::Index(2));
+            assert_eq!(
+                path.segments(),
+                vec![PathSegment::Key("frame".to_string()), PathSegment::Index(2)]
+            );
+        }
+        assert_eq!(path.segments(), vec![PathSegment::Key("frame".to_string())]);
+
+        drop(outer);
+        assert!(path.segments().is_empty());
+    }
+}